@@ -0,0 +1,430 @@
+//! Benchmark and regression testing against fixed workloads.
+//!
+//! A workload is a JSON file listing repositories to audit. Each run's
+//! metrics are written to a baseline file and, on subsequent runs, compared
+//! against it so CI can catch quality or performance regressions introduced
+//! by a model upgrade or prompt change.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+
+use crate::agent::{AgentConfig, CodeAnalysisAgent};
+use crate::models::Severity;
+use crate::repo::{self, CloneOptions};
+
+/// Arguments for the `bench` subcommand.
+#[derive(clap::Args, Debug, Clone)]
+pub struct BenchArgs {
+    /// Path to the workload JSON file
+    #[arg(long, value_name = "FILE")]
+    pub workload: PathBuf,
+
+    /// Path to the baseline results JSON (compared against, then overwritten)
+    #[arg(long, default_value = "bench_baseline.json", value_name = "FILE")]
+    pub baseline: PathBuf,
+
+    /// Maximum allowed percentage drift from the baseline before failing
+    #[arg(long, default_value = "10.0", value_name = "PERCENT")]
+    pub threshold: f64,
+
+    /// Optional endpoint to POST the results document to for a dashboard
+    #[arg(long, value_name = "URL")]
+    pub report_url: Option<String>,
+
+    /// Ollama API endpoint URL
+    #[arg(long, default_value = "http://localhost:11434", env = "OLLAMA_URL")]
+    pub ollama_url: String,
+}
+
+/// A single repository to benchmark, loaded from the workload file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WorkloadEntry {
+    pub name: String,
+    pub repo: String,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+    #[serde(default = "default_model")]
+    pub model: String,
+    pub expected: Option<ExpectedBounds>,
+}
+
+fn default_model() -> String {
+    "deepseek-coder:33b".to_string()
+}
+
+/// Pass/fail bounds a workload entry's metrics are expected to stay within.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExpectedBounds {
+    pub min_issues: Option<usize>,
+    pub max_issues: Option<usize>,
+    pub max_duration_seconds: Option<f64>,
+}
+
+/// Metrics captured from running a single workload entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryMetrics {
+    pub name: String,
+    pub model: String,
+    pub files_analyzed: usize,
+    pub duration_seconds: f64,
+    pub total_issues: usize,
+    pub issues_by_severity: HashMap<String, usize>,
+}
+
+/// The full results document for one bench run, written to and compared
+/// against the baseline file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResults {
+    pub generated_at: DateTime<Utc>,
+    pub entries: Vec<EntryMetrics>,
+}
+
+/// Run the `bench` subcommand: execute every entry in the workload, compare
+/// against the baseline and `expected` bounds, and report whether everything
+/// passed.
+pub async fn run_bench(args: BenchArgs) -> Result<bool> {
+    let workload = load_workload(&args.workload)?;
+    let baseline = load_baseline(&args.baseline)?;
+
+    let mut entries = Vec::with_capacity(workload.len());
+    let mut all_passed = true;
+
+    for entry in &workload {
+        println!("🏋️  Running workload: {}", entry.name);
+        let metrics = run_entry(entry, &args.ollama_url).await?;
+
+        if !check_bounds(entry, &metrics) {
+            all_passed = false;
+        }
+
+        if let Some(baseline_entry) = baseline
+            .as_ref()
+            .and_then(|b| b.entries.iter().find(|e| e.name == entry.name))
+        {
+            if !check_drift(baseline_entry, &metrics, args.threshold) {
+                all_passed = false;
+            }
+        } else {
+            println!("   (no baseline entry for {} yet)", entry.name);
+        }
+
+        entries.push(metrics);
+    }
+
+    let results = BenchResults {
+        generated_at: Utc::now(),
+        entries,
+    };
+
+    let output = serde_json::to_string_pretty(&results)?;
+    std::fs::write(&args.baseline, &output)
+        .with_context(|| format!("Failed to write baseline to {}", args.baseline.display()))?;
+
+    if let Some(ref url) = args.report_url {
+        report_results(url, &output).await?;
+    }
+
+    Ok(all_passed)
+}
+
+/// Clone (or reuse) a workload entry's repository and run a full audit pass,
+/// collecting the metrics we track for regressions.
+async fn run_entry(entry: &WorkloadEntry, ollama_url: &str) -> Result<EntryMetrics> {
+    let start_time = Instant::now();
+
+    let repo_path = if Path::new(&entry.repo).exists() {
+        PathBuf::from(&entry.repo)
+    } else {
+        let clone_options = CloneOptions {
+            branch: entry.branch.clone(),
+            depth: Some(1),
+            show_progress: false,
+            target_dir: None,
+        };
+        let path = repo::clone_repository(&entry.repo, clone_options)?.into_path();
+        if let Some(commit) = &entry.commit {
+            checkout_commit(&path, commit)?;
+        }
+        path
+    };
+
+    let agent_config = AgentConfig {
+        ollama_url: ollama_url.to_string(),
+        model_name: entry.model.clone(),
+        temperature: 0.1,
+        max_iterations: 50,
+        timeout_seconds: 300,
+        single_call_mode: false,
+        max_context_messages: 10,
+    };
+
+    let mut agent = CodeAnalysisAgent::new(agent_config, repo_path);
+    let reported_issues = agent.run_analysis().await?;
+
+    let mut issues_by_severity: HashMap<String, usize> = HashMap::new();
+    let mut files_analyzed: HashSet<String> = HashSet::new();
+    for issue in &reported_issues {
+        let severity = match issue.severity.to_lowercase().as_str() {
+            "critical" => Severity::Critical,
+            "high" => Severity::High,
+            "medium" => Severity::Medium,
+            _ => Severity::Low,
+        };
+        *issues_by_severity
+            .entry(format!("{:?}", severity))
+            .or_default() += 1;
+        files_analyzed.insert(issue.file_path.clone());
+    }
+
+    Ok(EntryMetrics {
+        name: entry.name.clone(),
+        model: entry.model.clone(),
+        files_analyzed: files_analyzed.len(),
+        duration_seconds: start_time.elapsed().as_secs_f64(),
+        total_issues: reported_issues.len(),
+        issues_by_severity,
+    })
+}
+
+/// Pin a freshly (shallow) cloned workload entry to `commit` so the run is
+/// reproducible and unrelated upstream changes don't show up as drift.
+/// Fetches the commit directly, since a depth-1 clone of the branch tip
+/// won't already have it, then checks it out detached.
+fn checkout_commit(repo_path: &Path, commit: &str) -> Result<()> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["fetch", "--depth", "1", "origin", commit])
+        .status()
+        .context("Failed to fetch the pinned workload commit")?;
+    if !status.success() {
+        bail!("git fetch of pinned commit '{}' failed", commit);
+    }
+
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["checkout", "--detach", "FETCH_HEAD"])
+        .status()
+        .context("Failed to check out the pinned workload commit")?;
+    if !status.success() {
+        bail!("git checkout of pinned commit '{}' failed", commit);
+    }
+    Ok(())
+}
+
+/// Check a run's metrics against the workload entry's `expected` bounds, if any.
+fn check_bounds(entry: &WorkloadEntry, metrics: &EntryMetrics) -> bool {
+    let Some(bounds) = entry.expected.as_ref() else {
+        return true;
+    };
+
+    let mut ok = true;
+    if let Some(min) = bounds.min_issues {
+        if metrics.total_issues < min {
+            eprintln!(
+                "❌ {}: total_issues {} below expected minimum {}",
+                entry.name, metrics.total_issues, min
+            );
+            ok = false;
+        }
+    }
+    if let Some(max) = bounds.max_issues {
+        if metrics.total_issues > max {
+            eprintln!(
+                "❌ {}: total_issues {} above expected maximum {}",
+                entry.name, metrics.total_issues, max
+            );
+            ok = false;
+        }
+    }
+    if let Some(max_duration) = bounds.max_duration_seconds {
+        if metrics.duration_seconds > max_duration {
+            eprintln!(
+                "❌ {}: duration {:.1}s exceeds expected maximum {:.1}s",
+                entry.name, metrics.duration_seconds, max_duration
+            );
+            ok = false;
+        }
+    }
+    ok
+}
+
+/// Check a run's metrics against the previous baseline for the same entry,
+/// failing if any tracked metric drifted beyond `threshold` percent.
+fn check_drift(baseline: &EntryMetrics, metrics: &EntryMetrics, threshold: f64) -> bool {
+    let mut ok = true;
+
+    if !within_threshold(
+        baseline.total_issues as f64,
+        metrics.total_issues as f64,
+        threshold,
+    ) {
+        eprintln!(
+            "❌ {}: total_issues drifted from {} to {} (> {:.1}%)",
+            metrics.name, baseline.total_issues, metrics.total_issues, threshold
+        );
+        ok = false;
+    }
+    if !within_threshold(
+        baseline.duration_seconds,
+        metrics.duration_seconds,
+        threshold,
+    ) {
+        eprintln!(
+            "❌ {}: duration_seconds drifted from {:.1} to {:.1} (> {:.1}%)",
+            metrics.name, baseline.duration_seconds, metrics.duration_seconds, threshold
+        );
+        ok = false;
+    }
+    if !within_threshold(
+        baseline.files_analyzed as f64,
+        metrics.files_analyzed as f64,
+        threshold,
+    ) {
+        eprintln!(
+            "❌ {}: files_analyzed drifted from {} to {} (> {:.1}%)",
+            metrics.name, baseline.files_analyzed, metrics.files_analyzed, threshold
+        );
+        ok = false;
+    }
+
+    let severities: HashSet<&String> = baseline
+        .issues_by_severity
+        .keys()
+        .chain(metrics.issues_by_severity.keys())
+        .collect();
+    let mut severities: Vec<&String> = severities.into_iter().collect();
+    severities.sort();
+    for severity in severities {
+        let before = *baseline.issues_by_severity.get(severity).unwrap_or(&0);
+        let after = *metrics.issues_by_severity.get(severity).unwrap_or(&0);
+        if !within_threshold(before as f64, after as f64, threshold) {
+            eprintln!(
+                "❌ {}: issues_by_severity[{}] drifted from {} to {} (> {:.1}%)",
+                metrics.name, severity, before, after, threshold
+            );
+            ok = false;
+        }
+    }
+
+    ok
+}
+
+/// Returns true if `actual` is within `threshold` percent of `baseline`.
+fn within_threshold(baseline: f64, actual: f64, threshold: f64) -> bool {
+    if baseline == 0.0 {
+        return actual == 0.0;
+    }
+    let drift = ((actual - baseline).abs() / baseline) * 100.0;
+    drift <= threshold
+}
+
+/// Load the workload file: a JSON array of `WorkloadEntry`.
+fn load_workload(path: &Path) -> Result<Vec<WorkloadEntry>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read workload file: {}", path.display()))?;
+    let entries: Vec<WorkloadEntry> = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse workload file: {}", path.display()))?;
+    if entries.is_empty() {
+        bail!("Workload file {} contains no entries", path.display());
+    }
+    Ok(entries)
+}
+
+/// Load the baseline results document, if one has been written yet.
+fn load_baseline(path: &Path) -> Result<Option<BenchResults>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read baseline file: {}", path.display()))?;
+    let results: BenchResults = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse baseline file: {}", path.display()))?;
+    Ok(Some(results))
+}
+
+/// POST the results document to the configured dashboard endpoint.
+async fn report_results(url: &str, body: &str) -> Result<()> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .with_context(|| format!("Failed to POST results to {}", url))?;
+
+    if !response.status().is_success() {
+        bail!("Report endpoint returned status {}", response.status());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_within_threshold() {
+        assert!(within_threshold(100.0, 105.0, 10.0));
+        assert!(!within_threshold(100.0, 120.0, 10.0));
+        assert!(within_threshold(0.0, 0.0, 10.0));
+        assert!(!within_threshold(0.0, 1.0, 10.0));
+    }
+
+    #[test]
+    fn test_check_bounds_within_range() {
+        let entry = WorkloadEntry {
+            name: "demo".to_string(),
+            repo: "local".to_string(),
+            branch: None,
+            commit: None,
+            model: default_model(),
+            expected: Some(ExpectedBounds {
+                min_issues: Some(1),
+                max_issues: Some(10),
+                max_duration_seconds: Some(60.0),
+            }),
+        };
+        let metrics = EntryMetrics {
+            name: "demo".to_string(),
+            model: default_model(),
+            files_analyzed: 3,
+            duration_seconds: 5.0,
+            total_issues: 4,
+            issues_by_severity: HashMap::new(),
+        };
+        assert!(check_bounds(&entry, &metrics));
+    }
+
+    #[test]
+    fn test_check_bounds_out_of_range() {
+        let entry = WorkloadEntry {
+            name: "demo".to_string(),
+            repo: "local".to_string(),
+            branch: None,
+            commit: None,
+            model: default_model(),
+            expected: Some(ExpectedBounds {
+                min_issues: Some(5),
+                max_issues: None,
+                max_duration_seconds: None,
+            }),
+        };
+        let metrics = EntryMetrics {
+            name: "demo".to_string(),
+            model: default_model(),
+            files_analyzed: 3,
+            duration_seconds: 5.0,
+            total_issues: 1,
+            issues_by_severity: HashMap::new(),
+        };
+        assert!(!check_bounds(&entry, &metrics));
+    }
+}
@@ -3,7 +3,7 @@
 //! This module handles all CLI argument parsing using clap,
 //! including validation and default values.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
 /// Code Auditor - AI-powered GitHub repository analyzer
@@ -16,8 +16,9 @@ use std::path::PathBuf;
 pub struct Args {
     /// GitHub repository URL to analyze
     ///
-    /// Supports HTTPS URLs (e.g., https://github.com/owner/repo.git)
-    #[arg(short, long, value_name = "URL")]
+    /// Supports HTTPS URLs (e.g., https://github.com/owner/repo.git).
+    /// Not required when running a subcommand such as `bench`.
+    #[arg(short, long, value_name = "URL", default_value = "")]
     pub repo: String,
 
     /// Ollama model to use for analysis
@@ -107,6 +108,74 @@ pub struct Args {
     /// Maximum context window size for chunking large files
     #[arg(long, default_value = "4000", value_name = "LINES")]
     pub max_chunk_lines: usize,
+
+    /// Subcommand to run instead of the default audit flow
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Generate fix patches from each issue's suggestion and apply them in place
+    ///
+    /// Like `--format patch`, writes the combined patch to `--output` in place
+    /// of the report; this flag also applies the hunks to the repository,
+    /// with a `.bak` backup of each original file.
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Exit with a nonzero status if any issue at or above this severity is found
+    ///
+    /// Intended for CI pipelines that should block on the audit results.
+    #[arg(long, value_name = "SEVERITY")]
+    pub fail_on: Option<FailOnSeverity>,
+
+    /// Print GitHub Actions workflow commands (`::error file=...,line=...::...`) for each issue
+    #[arg(long)]
+    pub github_annotations: bool,
+
+    /// Only analyze files/lines changed relative to this git ref
+    ///
+    /// Scopes the audit to a pull request's diff instead of the whole
+    /// repository: reported issues outside the changed hunks are dropped.
+    #[arg(long, value_name = "REF")]
+    pub diff_base: Option<String>,
+
+    /// Clone the repository inside an isolated container (Docker or Podman)
+    ///
+    /// Use when auditing untrusted third-party repositories so `git clone`
+    /// can't touch the host network or filesystem beyond its own sandbox.
+    /// This only isolates the clone step: analysis still reads the checked
+    /// out files on the host afterward, so it is not a substitute for
+    /// sandboxing the whole audit.
+    #[arg(long)]
+    pub sandbox: bool,
+
+    /// Base image used for the sandboxed clone container
+    #[arg(long, default_value = "debian:bookworm-slim", value_name = "IMAGE")]
+    pub sandbox_image: String,
+
+    /// CPU limit passed to the sandbox container (e.g. "2", "0.5")
+    #[arg(long, default_value = "2", value_name = "CPUS")]
+    pub sandbox_cpus: String,
+
+    /// Memory limit passed to the sandbox container (e.g. "2g", "512m")
+    #[arg(long, default_value = "2g", value_name = "MEMORY")]
+    pub sandbox_memory: String,
+}
+
+/// Minimum severity threshold for `--fail-on`, ordered from least to most severe
+/// so `FailOnSeverity` values can be compared directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum FailOnSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Subcommands supported alongside the default audit flow.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Run a benchmark/regression workload against a fixed set of repositories
+    Bench(crate::bench::BenchArgs),
 }
 
 /// Output format for the report.
@@ -117,6 +186,10 @@ pub enum OutputFormat {
     Markdown,
     /// JSON format
     Json,
+    /// Unified diff patch generated from each issue's suggestion
+    Patch,
+    /// SARIF 2.1.0, for GitHub/GitLab code-scanning dashboards
+    Sarif,
 }
 
 impl Args {
@@ -127,6 +200,12 @@ impl Args {
 
     /// Validate the parsed arguments.
     pub fn validate(&self) -> Result<(), String> {
+        // Subcommands perform their own validation independently of the
+        // default audit flow, which needs a `--repo`/`--local`.
+        if self.command.is_some() {
+            return Ok(());
+        }
+
         // Validate repository URL format
         if !self.repo.starts_with("https://") && !self.repo.starts_with("git@") {
             if self.local.is_none() {
@@ -256,6 +335,15 @@ mod tests {
             format: OutputFormat::Markdown,
             temperature: 0.1,
             max_chunk_lines: 4000,
+            command: None,
+            fix: false,
+            fail_on: None,
+            github_annotations: false,
+            diff_base: None,
+            sandbox: false,
+            sandbox_image: "debian:bookworm-slim".to_string(),
+            sandbox_cpus: "2".to_string(),
+            sandbox_memory: "2g".to_string(),
         };
 
         let exts = args.effective_extensions();
@@ -284,6 +372,15 @@ mod tests {
             format: OutputFormat::Markdown,
             temperature: 0.1,
             max_chunk_lines: 4000,
+            command: None,
+            fix: false,
+            fail_on: None,
+            github_annotations: false,
+            diff_base: None,
+            sandbox: false,
+            sandbox_image: "debian:bookworm-slim".to_string(),
+            sandbox_cpus: "2".to_string(),
+            sandbox_memory: "2g".to_string(),
         };
 
         assert!(args.validate().is_err());
@@ -309,6 +406,15 @@ mod tests {
             format: OutputFormat::Markdown,
             temperature: 0.1,
             max_chunk_lines: 4000,
+            command: None,
+            fix: false,
+            fail_on: None,
+            github_annotations: false,
+            diff_base: None,
+            sandbox: false,
+            sandbox_image: "debian:bookworm-slim".to_string(),
+            sandbox_cpus: "2".to_string(),
+            sandbox_memory: "2g".to_string(),
         };
 
         assert!(args.validate().is_err());
@@ -334,6 +440,15 @@ mod tests {
             format: OutputFormat::Markdown,
             temperature: 0.1,
             max_chunk_lines: 4000,
+            command: None,
+            fix: false,
+            fail_on: None,
+            github_annotations: false,
+            diff_base: None,
+            sandbox: false,
+            sandbox_image: "debian:bookworm-slim".to_string(),
+            sandbox_cpus: "2".to_string(),
+            sandbox_memory: "2g".to_string(),
         };
 
         assert_eq!(args.log_level(), tracing::Level::INFO);
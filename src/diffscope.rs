@@ -0,0 +1,217 @@
+//! Diff-scoped analysis: restricts an audit to the files and added-line
+//! ranges changed relative to a base git ref (`--diff-base`), for auditing
+//! pull requests instead of whole repositories.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// A contiguous range of added lines within one changed file.
+#[derive(Debug, Clone, Copy)]
+pub struct ChangedHunk {
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+/// A file changed relative to the base ref, with its added-line ranges.
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    pub path: String,
+    pub hunks: Vec<ChangedHunk>,
+}
+
+/// Fetch `base_ref` into the (possibly shallow) clone so it can be diffed,
+/// then compute the changed files and added-line ranges relative to HEAD.
+///
+/// `--depth 1` clones perform a single-branch shallow clone, which narrows
+/// `origin`'s fetch refspec to only the checked-out branch. Fetching a
+/// different ref by name then only populates `FETCH_HEAD`, not a local
+/// branch or `origin/<base_ref>` — so the diff below is taken against
+/// `FETCH_HEAD` rather than `base_ref` itself.
+///
+/// Uses the two-dot `FETCH_HEAD..HEAD` form, not three-dot: both sides come
+/// from independent depth-1 shallow fetches with no shared history locally,
+/// so there is no merge-base for `git diff` to compute from (three-dot fails
+/// with "no merge base"). Two-dot diffs the two tips' content directly.
+pub fn compute_changed_files(repo_path: &Path, base_ref: &str) -> Result<Vec<ChangedFile>> {
+    fetch_base_ref(repo_path, base_ref)?;
+
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["diff", "--unified=0", "FETCH_HEAD..HEAD"])
+        .output()
+        .context("Failed to run git diff against the diff base ref")?;
+
+    if !output.status.success() {
+        bail!(
+            "git diff against '{}' failed: {}",
+            base_ref,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(parse_unified_diff(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Shallow-fetch the base ref so a depth-1 clone has enough history to diff
+/// against it. Fetched into `FETCH_HEAD` rather than a named local ref,
+/// since `base_ref` may be a branch, tag, or commit.
+fn fetch_base_ref(repo_path: &Path, base_ref: &str) -> Result<()> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["fetch", "--depth", "1", "origin", base_ref])
+        .status()
+        .context("Failed to fetch the diff base ref")?;
+
+    if !status.success() {
+        bail!("git fetch of diff base ref '{}' failed", base_ref);
+    }
+    Ok(())
+}
+
+/// Parse `git diff --unified=0` output into per-file added-line ranges.
+fn parse_unified_diff(diff: &str) -> Vec<ChangedFile> {
+    let mut files = Vec::new();
+    let mut current_path: Option<String> = None;
+    let mut current_hunks: Vec<ChangedHunk> = Vec::new();
+
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            if let Some(path) = current_path.take() {
+                files.push(ChangedFile {
+                    path,
+                    hunks: std::mem::take(&mut current_hunks),
+                });
+            }
+            current_path = path.strip_prefix("b/").map(str::to_string);
+        } else if let Some(hunk) = line.strip_prefix("@@ ") {
+            if current_path.is_some() {
+                if let Some(range) = parse_hunk_header(hunk) {
+                    current_hunks.push(range);
+                }
+            }
+        }
+    }
+
+    if let Some(path) = current_path {
+        files.push(ChangedFile {
+            path,
+            hunks: current_hunks,
+        });
+    }
+
+    files
+}
+
+/// Parse the `+start,count` portion of a `@@ -a,b +start,count @@` header.
+fn parse_hunk_header(hunk: &str) -> Option<ChangedHunk> {
+    let plus_part = hunk.split('+').nth(1)?;
+    let spec = plus_part.split(' ').next()?;
+    let mut parts = spec.splitn(2, ',');
+    let start: usize = parts.next()?.parse().ok()?;
+    let count: usize = match parts.next() {
+        Some(c) => c.parse().ok()?,
+        None => 1,
+    };
+
+    if count == 0 {
+        // A pure deletion hunk adds nothing to scan.
+        return None;
+    }
+    Some(ChangedHunk {
+        start_line: start,
+        end_line: start + count - 1,
+    })
+}
+
+/// Returns true if `file_path`/`line` falls inside one of the changed hunks.
+pub fn is_in_changed_hunks(changed: &[ChangedFile], file_path: &str, line: usize) -> bool {
+    changed
+        .iter()
+        .filter(|f| f.path == file_path)
+        .any(|f| f.hunks.iter().any(|h| line >= h.start_line && line <= h.end_line))
+}
+
+/// Returns the set of changed file paths, for scoping which files are sent to the agent.
+pub fn changed_file_paths(changed: &[ChangedFile]) -> Vec<String> {
+    changed.iter().map(|f| f.path.clone()).collect()
+}
+
+/// Copy only the changed files out of `repo_path` into a fresh temp
+/// directory, mirroring their relative paths, and return that directory.
+///
+/// The agent scans whatever directory it's pointed at, so handing it this
+/// scoped copy instead of the full clone is what actually cuts the work
+/// (and runtime) down to the PR's diff, rather than just filtering the
+/// issues it reports after the fact.
+pub fn stage_scoped_copy(repo_path: &Path, changed: &[ChangedFile]) -> Result<PathBuf> {
+    let staging_dir =
+        std::env::temp_dir().join(format!("code-auditor-diff-scope-{}", std::process::id()));
+    std::fs::create_dir_all(&staging_dir)
+        .with_context(|| format!("Failed to create diff-scope staging directory {}", staging_dir.display()))?;
+
+    for file in changed {
+        let src = repo_path.join(&file.path);
+        if !src.is_file() {
+            // Deleted or renamed-away in this diff; nothing to scan.
+            continue;
+        }
+        let dest = staging_dir.join(&file.path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
+        std::fs::copy(&src, &dest)
+            .with_context(|| format!("Failed to stage changed file {}", file.path))?;
+    }
+
+    Ok(staging_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_DIFF: &str = "\
+diff --git a/src/main.rs b/src/main.rs
+index 1111111..2222222 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -10,0 +11,2 @@ fn main() {
++    let x = 1;
++    let y = 2;
+diff --git a/src/lib.rs b/src/lib.rs
+index 3333333..4444444 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -5 +5 @@ pub fn lib_fn() {
+-    old_call();
++    new_call();
+";
+
+    #[test]
+    fn test_parse_unified_diff() {
+        let files = parse_unified_diff(SAMPLE_DIFF);
+        assert_eq!(files.len(), 2);
+
+        let main_rs = files.iter().find(|f| f.path == "src/main.rs").unwrap();
+        assert_eq!(main_rs.hunks.len(), 1);
+        assert_eq!(main_rs.hunks[0].start_line, 11);
+        assert_eq!(main_rs.hunks[0].end_line, 12);
+
+        let lib_rs = files.iter().find(|f| f.path == "src/lib.rs").unwrap();
+        assert_eq!(lib_rs.hunks[0].start_line, 5);
+        assert_eq!(lib_rs.hunks[0].end_line, 5);
+    }
+
+    #[test]
+    fn test_is_in_changed_hunks() {
+        let changed = parse_unified_diff(SAMPLE_DIFF);
+        assert!(is_in_changed_hunks(&changed, "src/main.rs", 11));
+        assert!(is_in_changed_hunks(&changed, "src/main.rs", 12));
+        assert!(!is_in_changed_hunks(&changed, "src/main.rs", 13));
+        assert!(!is_in_changed_hunks(&changed, "src/other.rs", 11));
+    }
+}
@@ -0,0 +1,384 @@
+//! Auto-fix pipeline: turns an `Issue::suggestion` into an applied patch.
+//!
+//! For every issue that carries a suggestion, the original source span is
+//! re-sent to the model together with the suggestion so it can produce a
+//! concrete replacement. Replacements are diffed against the original file
+//! to build a unified `.patch` document, and (with `--fix`) applied in
+//! place with a `.bak` backup of the untouched file.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::models::{Issue, Report};
+
+/// Configuration needed to re-prompt the model for a fix.
+#[derive(Debug, Clone)]
+pub struct FixConfig {
+    pub ollama_url: String,
+    pub model_name: String,
+    pub temperature: f32,
+    /// Run a best-effort syntax sanity check before writing a fixed file.
+    pub syntax_check: bool,
+}
+
+/// A single contiguous line-range replacement derived from one issue.
+#[derive(Debug, Clone)]
+struct Hunk {
+    start_line: usize,
+    end_line: usize,
+}
+
+/// The result of fixing one file: the combined diff plus enough state to
+/// apply it in place.
+#[derive(Debug, Clone)]
+pub struct FileDiff {
+    pub file_path: String,
+    pub unified_diff: String,
+    original_content: String,
+    new_content: String,
+    pub skipped_overlaps: usize,
+}
+
+/// For every issue with a `suggestion`, ask the model for a replacement code
+/// block and build a per-file diff. Issues without a suggestion, or whose
+/// span overlaps one already accepted in the same file, are skipped.
+pub async fn generate_fix_patches(report: &Report, repo_path: &Path, config: &FixConfig) -> Result<Vec<FileDiff>> {
+    let mut diffs = Vec::new();
+
+    for file in &report.files {
+        let fixable: Vec<&Issue> = file
+            .issues
+            .iter()
+            .filter(|issue| issue.suggestion.is_some())
+            .collect();
+
+        if fixable.is_empty() {
+            continue;
+        }
+
+        let full_path = repo_path.join(&file.path);
+        let original_content = std::fs::read_to_string(&full_path)
+            .with_context(|| format!("Failed to read {} for auto-fix", full_path.display()))?;
+        let original_lines: Vec<&str> = original_content.lines().collect();
+
+        let (accepted, skipped_overlaps) = accept_non_overlapping_hunks(fixable);
+        if accepted.is_empty() {
+            continue;
+        }
+
+        let mut hunks_with_replacements: Vec<(Hunk, String)> = Vec::with_capacity(accepted.len());
+        for (issue, hunk) in accepted {
+            let Some((span_start, span_end)) = clamp_hunk_span(original_lines.len(), &hunk) else {
+                warn!(
+                    "Skipping out-of-range fix span {}:{}-{} (file has {} line(s))",
+                    file.path,
+                    hunk.start_line,
+                    hunk.end_line,
+                    original_lines.len()
+                );
+                continue;
+            };
+            let original_span = original_lines[span_start..span_end].join("\n");
+
+            let replacement = generate_replacement(config, issue, &original_span).await?;
+            hunks_with_replacements.push((hunk, replacement));
+        }
+
+        if hunks_with_replacements.is_empty() {
+            continue;
+        }
+
+        let new_lines = splice_hunks(&original_lines, hunks_with_replacements);
+        let new_content = new_lines.join("\n") + "\n";
+
+        if config.syntax_check && !basic_syntax_check(&new_content) {
+            warn!(
+                "Syntax check failed for {} after applying fixes; skipping file",
+                file.path
+            );
+            continue;
+        }
+
+        let unified_diff = similar::TextDiff::from_lines(&original_content, &new_content)
+            .unified_diff()
+            .context_radius(3)
+            .header(&file.path, &file.path)
+            .to_string();
+
+        diffs.push(FileDiff {
+            file_path: file.path.clone(),
+            unified_diff,
+            original_content,
+            new_content,
+            skipped_overlaps,
+        });
+    }
+
+    Ok(diffs)
+}
+
+/// Re-prompt the model with the original span and the issue's suggestion to
+/// produce a concrete replacement code block.
+async fn generate_replacement(config: &FixConfig, issue: &Issue, original_span: &str) -> Result<String> {
+    let suggestion = issue.suggestion.as_deref().unwrap_or_default();
+    let prompt = format!(
+        "You are fixing a single issue in a source file.\n\
+         Issue: {title}\n\
+         Description: {description}\n\
+         Suggested fix: {suggestion}\n\n\
+         Original code:\n```\n{original}\n```\n\n\
+         Reply with ONLY the replacement code for this span, \
+         preserving indentation, with no markdown fences and no commentary.",
+        title = issue.title,
+        description = issue.description,
+        suggestion = suggestion,
+        original = original_span,
+    );
+
+    let raw = query_model(config, &prompt).await?;
+    Ok(strip_code_fences(&raw))
+}
+
+/// A single `/api/generate` call to Ollama, independent of the agentic
+/// tool-calling loop used for analysis.
+async fn query_model(config: &FixConfig, prompt: &str) -> Result<String> {
+    #[derive(Deserialize)]
+    struct GenerateResponse {
+        response: String,
+    }
+
+    let client = reqwest::Client::new();
+    let body = json!({
+        "model": config.model_name,
+        "prompt": prompt,
+        "stream": false,
+        "options": { "temperature": config.temperature },
+    });
+
+    let response = client
+        .post(format!("{}/api/generate", config.ollama_url))
+        .json(&body)
+        .send()
+        .await
+        .context("Failed to reach Ollama for auto-fix generation")?
+        .error_for_status()
+        .context("Ollama returned an error response during auto-fix generation")?
+        .json::<GenerateResponse>()
+        .await
+        .context("Failed to parse Ollama response during auto-fix generation")?;
+
+    Ok(response.response)
+}
+
+/// Strip a leading/trailing ```` ``` ```` fence (with optional language tag) if the model added one anyway.
+fn strip_code_fences(text: &str) -> String {
+    let trimmed = text.trim();
+    if let Some(inner) = trimmed.strip_prefix("```") {
+        let without_lang = inner.splitn(2, '\n').nth(1).unwrap_or(inner);
+        without_lang
+            .trim_end()
+            .strip_suffix("```")
+            .unwrap_or(without_lang)
+            .trim()
+            .to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Accept issues in ascending start-line order, rejecting any whose span
+/// overlaps one already accepted for the same file. Returns the accepted
+/// `(issue, hunk)` pairs in ascending order plus how many were skipped for
+/// overlapping.
+fn accept_non_overlapping_hunks<'a>(fixable: Vec<&'a Issue>) -> (Vec<(&'a Issue, Hunk)>, usize) {
+    let mut accepted: Vec<(&Issue, Hunk)> = Vec::new();
+    let mut skipped_overlaps = 0usize;
+
+    let mut sorted = fixable;
+    sorted.sort_by_key(|issue| issue.start_line);
+
+    for issue in sorted {
+        let start_line = issue.start_line.max(1);
+        let end_line = issue.end_line.unwrap_or(start_line).max(start_line);
+
+        let overlaps = accepted
+            .iter()
+            .any(|(_, hunk)| start_line <= hunk.end_line && end_line >= hunk.start_line);
+        if overlaps {
+            warn!(
+                "Skipping overlapping fix span {}:{}-{}",
+                issue.file_path, start_line, end_line
+            );
+            skipped_overlaps += 1;
+            continue;
+        }
+
+        accepted.push((issue, Hunk { start_line, end_line }));
+    }
+
+    (accepted, skipped_overlaps)
+}
+
+/// Clamp `hunk`'s line range to valid slice indices into a `lines_len`-long
+/// slice, or `None` if the span no longer fits (e.g. an LLM-reported line
+/// number exceeding the file's current length).
+fn clamp_hunk_span(lines_len: usize, hunk: &Hunk) -> Option<(usize, usize)> {
+    let span_start = hunk.start_line - 1;
+    if span_start >= lines_len {
+        return None;
+    }
+    let span_end = hunk.end_line.min(lines_len);
+    if span_end < span_start {
+        return None;
+    }
+    Some((span_start, span_end))
+}
+
+/// Splice each `(hunk, replacement)` into `original_lines`, applying hunks
+/// in descending start-line order so earlier edits don't shift the line
+/// numbers of hunks still to come.
+fn splice_hunks(original_lines: &[&str], mut hunks: Vec<(Hunk, String)>) -> Vec<String> {
+    hunks.sort_by(|a, b| b.0.start_line.cmp(&a.0.start_line));
+
+    let mut new_lines: Vec<String> = original_lines.iter().map(|l| l.to_string()).collect();
+    for (hunk, replacement) in &hunks {
+        if let Some((span_start, span_end)) = clamp_hunk_span(new_lines.len(), hunk) {
+            let replacement_lines: Vec<String> = replacement.lines().map(|l| l.to_string()).collect();
+            new_lines.splice(span_start..span_end, replacement_lines);
+        }
+    }
+    new_lines
+}
+
+/// A best-effort, language-agnostic sanity check: rejects replacements that
+/// leave braces/parens/brackets unbalanced. Not a real parser, just a guard
+/// against obviously truncated model output.
+fn basic_syntax_check(content: &str) -> bool {
+    let mut stack = Vec::new();
+    for ch in content.chars() {
+        match ch {
+            '(' | '[' | '{' => stack.push(ch),
+            ')' => {
+                if stack.pop() != Some('(') {
+                    return false;
+                }
+            }
+            ']' => {
+                if stack.pop() != Some('[') {
+                    return false;
+                }
+            }
+            '}' => {
+                if stack.pop() != Some('{') {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    stack.is_empty()
+}
+
+/// Concatenate every file's unified diff into a single combined `.patch` document.
+pub fn write_combined_patch(diffs: &[FileDiff], output_path: &Path) -> Result<()> {
+    let combined: String = diffs.iter().map(|d| d.unified_diff.clone()).collect();
+    std::fs::write(output_path, combined)
+        .with_context(|| format!("Failed to write patch to {}", output_path.display()))
+}
+
+/// Apply every file diff in place, writing a `.bak` backup of the original
+/// file alongside it first.
+pub fn apply_patches(diffs: &[FileDiff], repo_path: &Path) -> Result<()> {
+    for diff in diffs {
+        let full_path = repo_path.join(&diff.file_path);
+        let backup_path = PathBuf::from(format!("{}.bak", full_path.display()));
+
+        std::fs::write(&backup_path, &diff.original_content)
+            .with_context(|| format!("Failed to write backup {}", backup_path.display()))?;
+        std::fs::write(&full_path, &diff.new_content)
+            .with_context(|| format!("Failed to write fixed file {}", full_path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Severity;
+
+    fn issue(start_line: usize, end_line: Option<usize>) -> Issue {
+        Issue {
+            file_path: "src/lib.rs".to_string(),
+            start_line,
+            end_line,
+            severity: Severity::Medium,
+            category: "style".to_string(),
+            title: "Example issue".to_string(),
+            description: "An example issue for tests.".to_string(),
+            suggestion: Some("fix it".to_string()),
+            code_snippet: None,
+        }
+    }
+
+    #[test]
+    fn test_accept_non_overlapping_hunks_skips_overlaps() {
+        let a = issue(1, Some(2));
+        let b = issue(2, Some(3)); // overlaps `a`
+        let c = issue(5, Some(5));
+        let d = issue(10, Some(10));
+
+        let (accepted, skipped) = accept_non_overlapping_hunks(vec![&a, &b, &c, &d]);
+
+        assert_eq!(skipped, 1);
+        let start_lines: Vec<usize> = accepted.iter().map(|(_, h)| h.start_line).collect();
+        assert_eq!(start_lines, vec![1, 5, 10]);
+    }
+
+    #[test]
+    fn test_splice_hunks_applies_in_descending_order() {
+        let original_lines = vec!["one", "two", "three", "four", "five"];
+        let hunks = vec![
+            (Hunk { start_line: 2, end_line: 2 }, "TWO".to_string()),
+            (Hunk { start_line: 4, end_line: 5 }, "FOUR-FIVE".to_string()),
+        ];
+
+        let new_lines = splice_hunks(&original_lines, hunks);
+
+        assert_eq!(new_lines, vec!["one", "TWO", "three", "FOUR-FIVE"]);
+    }
+
+    #[test]
+    fn test_splice_hunks_skips_out_of_range_span() {
+        let original_lines = vec!["one", "two"];
+        let hunks = vec![(Hunk { start_line: 10, end_line: 11 }, "NOPE".to_string())];
+
+        let new_lines = splice_hunks(&original_lines, hunks);
+
+        assert_eq!(new_lines, vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_strip_code_fences_with_language() {
+        let text = "```rust\nlet x = 1;\n```";
+        assert_eq!(strip_code_fences(text), "let x = 1;");
+    }
+
+    #[test]
+    fn test_strip_code_fences_plain() {
+        let text = "let x = 1;";
+        assert_eq!(strip_code_fences(text), "let x = 1;");
+    }
+
+    #[test]
+    fn test_basic_syntax_check_balanced() {
+        assert!(basic_syntax_check("fn main() { let v = [1, 2, (3)]; }"));
+    }
+
+    #[test]
+    fn test_basic_syntax_check_unbalanced() {
+        assert!(!basic_syntax_check("fn main() { let v = [1, 2;"));
+    }
+}
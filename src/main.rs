@@ -5,16 +5,20 @@
 
 mod agent;
 mod analysis;
+mod bench;
 mod cli;
 mod config;
+mod diffscope;
+mod fixer;
 mod models;
 mod repo;
 mod report;
+mod sandbox;
 mod scanner;
 
 use anyhow::{Context, Result};
 use chrono::Utc;
-use cli::{Args, OutputFormat};
+use cli::{Args, Command, FailOnSeverity, OutputFormat};
 use config::Config;
 use models::{AnalyzedFile, Issue, IssueSummary, Report, ReportMetadata, Severity};
 use std::path::PathBuf;
@@ -39,6 +43,22 @@ async fn main() -> Result<()> {
     info!("Code Auditor v{}", env!("CARGO_PKG_VERSION"));
     debug!("Arguments: {:?}", args);
 
+    // Dispatch to a subcommand if one was given, otherwise run the normal audit flow
+    if let Some(Command::Bench(bench_args)) = args.command.clone() {
+        return match bench::run_bench(bench_args).await {
+            Ok(true) => Ok(()),
+            Ok(false) => {
+                eprintln!("\n‚ùå Bench run failed its expected bounds or drifted beyond threshold");
+                std::process::exit(1);
+            }
+            Err(e) => {
+                error!("Bench run failed: {}", e);
+                eprintln!("\n‚ùå Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+    }
+
     // Run the audit
     match run_audit(args).await {
         Ok(output_path) => {
@@ -85,6 +105,18 @@ async fn run_audit(args: Args) -> Result<PathBuf> {
     let repo_path = get_repository(&args).await?;
     info!("Repository at: {}", repo_path.display());
 
+    // If scoped to a PR diff, compute the changed files/hunks up front so we
+    // can both narrow what's sent to the agent and filter the final report.
+    let changed_files = match &args.diff_base {
+        Some(base_ref) => {
+            println!("Scoping analysis to changes since: {}", base_ref);
+            let changed = diffscope::compute_changed_files(&repo_path, base_ref)?;
+            println!("   {} file(s) changed", changed.len());
+            Some(changed)
+        }
+        None => None,
+    };
+
     // Try to load config from repository
     if let Ok(Some(repo_config)) = Config::load_from_repo(&repo_path) {
         info!("Found .code-auditor.toml in repository");
@@ -115,7 +147,15 @@ async fn run_audit(args: Args) -> Result<PathBuf> {
         max_context_messages: 10, // Sliding window to prevent context overflow
     };
 
-    let mut agent = agent::CodeAnalysisAgent::new(agent_config, repo_path.clone());
+    // When diff-scoped, point the agent at a staged copy containing only the
+    // changed files instead of the full clone, so it scans (and spends
+    // tokens on) just the PR's diff rather than the whole repository.
+    let agent_repo_path = match &changed_files {
+        Some(changed) => diffscope::stage_scoped_copy(&repo_path, changed)?,
+        None => repo_path.clone(),
+    };
+
+    let mut agent = agent::CodeAnalysisAgent::new(agent_config, agent_repo_path);
 
     // Step 3: Run the agentic analysis
     println!("\nüî¨ Running code analysis...");
@@ -148,6 +188,16 @@ async fn run_audit(args: Args) -> Result<PathBuf> {
         })
         .collect();
 
+    // If diff-scoped, drop any issue outside the changed hunks so the report
+    // only covers what the PR actually touches.
+    let issues: Vec<Issue> = match &changed_files {
+        Some(changed) => issues
+            .into_iter()
+            .filter(|issue| diffscope::is_in_changed_hunks(changed, &issue.file_path, issue.start_line))
+            .collect(),
+        None => issues,
+    };
+
     // Step 5: Build the report
     println!("\nüìù Generating report...");
 
@@ -198,16 +248,13 @@ async fn run_audit(args: Args) -> Result<PathBuf> {
         ],
     };
 
-    // Step 6: Generate and save the report
-    let output = match args.format {
-        OutputFormat::Json => report::generate_json_report(&report)?,
-        OutputFormat::Markdown => report::generate_markdown_report(&report),
-    };
-
-    std::fs::write(&args.output, &output)
-        .with_context(|| format!("Failed to write report to {}", args.output.display()))?;
+    // Step 6: GitHub annotations, the summary, and the --fail-on gate apply no
+    // matter which output mode follows, so --fix/--format patch don't
+    // silently skip CI gating the way the early-return branch below would.
+    if args.github_annotations {
+        print_github_annotations(&report);
+    }
 
-    // Print summary
     println!("\nüìä Analysis Summary:");
     println!("   Files with issues: {}", report.files.len());
     println!("   Total issues: {}", summary.total);
@@ -217,9 +264,86 @@ async fn run_audit(args: Args) -> Result<PathBuf> {
     );
     println!("   Duration: {:.1}s", duration);
 
+    if let Some(threshold) = args.fail_on {
+        let blocking = report
+            .files
+            .iter()
+            .flat_map(|f| f.issues.iter())
+            .any(|issue| severity_meets_threshold(&issue.severity, threshold));
+        if blocking {
+            eprintln!(
+                "\n‚ùå Found issue(s) at or above --fail-on {:?} severity",
+                threshold
+            );
+            std::process::exit(1);
+        }
+    }
+
+    // Step 7: Generate and save the report
+    if args.fix || matches!(args.format, OutputFormat::Patch) {
+        println!("\nüî© Generating fix patches from issue suggestions...");
+
+        let fix_config = fixer::FixConfig {
+            ollama_url: config.model.ollama_url.clone(),
+            model_name: config.model.name.clone(),
+            temperature: config.model.temperature,
+            syntax_check: true,
+        };
+        let diffs = fixer::generate_fix_patches(&report, &repo_path, &fix_config).await?;
+
+        fixer::write_combined_patch(&diffs, &args.output)?;
+        println!("   Patch written to: {}", args.output.display());
+
+        if args.fix {
+            fixer::apply_patches(&diffs, &repo_path)?;
+            println!("   Applied {} file(s), originals backed up with .bak", diffs.len());
+        }
+
+        return Ok(args.output);
+    }
+
+    let output = match args.format {
+        OutputFormat::Json => report::generate_json_report(&report)?,
+        OutputFormat::Markdown => report::generate_markdown_report(&report),
+        OutputFormat::Sarif => report::generate_sarif_report(&report)?,
+        OutputFormat::Patch => unreachable!("handled above"),
+    };
+
+    std::fs::write(&args.output, &output)
+        .with_context(|| format!("Failed to write report to {}", args.output.display()))?;
+
     Ok(args.output)
 }
 
+/// Print a GitHub Actions workflow command for every issue so they show up
+/// as inline annotations on the PR diff.
+fn print_github_annotations(report: &Report) {
+    for file in &report.files {
+        for issue in &file.issues {
+            let level = match &issue.severity {
+                Severity::Critical | Severity::High => "error",
+                Severity::Medium => "warning",
+                Severity::Low => "notice",
+            };
+            println!(
+                "::{} file={},line={}::{}: {}",
+                level, issue.file_path, issue.start_line, issue.title, issue.description
+            );
+        }
+    }
+}
+
+/// Returns true if `severity` is at or above the `--fail-on` threshold.
+fn severity_meets_threshold(severity: &Severity, threshold: FailOnSeverity) -> bool {
+    let rank = match severity {
+        Severity::Critical => FailOnSeverity::Critical,
+        Severity::High => FailOnSeverity::High,
+        Severity::Medium => FailOnSeverity::Medium,
+        Severity::Low => FailOnSeverity::Low,
+    };
+    rank >= threshold
+}
+
 /// Load configuration from file or use defaults.
 fn load_config(args: &Args) -> Result<Config> {
     // Try explicit config path
@@ -253,6 +377,18 @@ async fn get_repository(args: &Args) -> Result<PathBuf> {
         return Ok(local.clone());
     }
 
+    if args.sandbox {
+        info!("Cloning repository inside a sandbox container: {}", args.repo);
+        warn!("--sandbox only isolates the clone step; analysis still reads the checked-out files on the host");
+        let sandbox_config = sandbox::SandboxConfig {
+            image: args.sandbox_image.clone(),
+            cpu_limit: args.sandbox_cpus.clone(),
+            memory_limit: args.sandbox_memory.clone(),
+            read_only_after_clone: true,
+        };
+        return sandbox::sandboxed_clone(&args.repo, args.branch.as_deref(), &sandbox_config);
+    }
+
     // Clone the repository
     info!("Cloning repository: {}", args.repo);
 
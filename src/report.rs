@@ -0,0 +1,184 @@
+//! Report generation: renders a `Report` into the various output formats
+//! the CLI can write (`--format`).
+
+use anyhow::Result;
+use serde_json::json;
+
+use crate::models::{Issue, Report, Severity};
+
+/// Render a report as pretty-printed JSON.
+pub fn generate_json_report(report: &Report) -> Result<String> {
+    Ok(serde_json::to_string_pretty(report)?)
+}
+
+/// Render a report as a human-readable Markdown document.
+pub fn generate_markdown_report(report: &Report) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!("# Code Audit Report: {}\n\n", report.metadata.repo_url));
+    out.push_str(&format!(
+        "**Date:** {}  \n**Model:** {}  \n**Files analyzed:** {}  \n**Duration:** {:.1}s\n\n",
+        report.metadata.analysis_date.to_rfc3339(),
+        report.metadata.model_used,
+        report.metadata.files_analyzed,
+        report.metadata.duration_seconds,
+    ));
+
+    out.push_str("## Project Overview\n\n");
+    out.push_str(&report.project_overview);
+    out.push_str("\n\n");
+
+    out.push_str("## Summary\n\n");
+    out.push_str(&format!(
+        "- 🔴 Critical: {}\n- 🟠 High: {}\n- 🟡 Medium: {}\n- 🟢 Low: {}\n- **Total: {}**\n\n",
+        report.summary.critical,
+        report.summary.high,
+        report.summary.medium,
+        report.summary.low,
+        report.summary.total,
+    ));
+
+    out.push_str("## Issues by File\n\n");
+    for file in &report.files {
+        if file.issues.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("### `{}`\n\n", file.path));
+        for issue in &file.issues {
+            out.push_str(&format!(
+                "- **[{:?}] {}** (line {}) — {}\n",
+                issue.severity, issue.title, issue.start_line, issue.description
+            ));
+            if let Some(ref suggestion) = issue.suggestion {
+                out.push_str(&format!("  - Suggestion: {}\n", suggestion));
+            }
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Recommendations\n\n");
+    for recommendation in &report.recommendations {
+        out.push_str(&format!("- {}\n", recommendation));
+    }
+
+    out
+}
+
+/// Render a report as SARIF 2.1.0, suitable for GitHub/GitLab code-scanning
+/// dashboards.
+///
+/// `rules` are derived from the distinct issue categories so each category
+/// shows up once in the tool's rule list regardless of how many issues map
+/// to it.
+pub fn generate_sarif_report(report: &Report) -> Result<String> {
+    let issues: Vec<_> = report.files.iter().flat_map(|f| f.issues.iter()).collect();
+    let categories = dedupe_categories(&issues);
+
+    let rules: Vec<_> = categories
+        .iter()
+        .map(|category| {
+            json!({
+                "id": category,
+                "name": category,
+                "shortDescription": { "text": category },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = issues
+        .iter()
+        .map(|issue| {
+            json!({
+                "ruleId": issue.category,
+                "level": sarif_level(&issue.severity),
+                "message": { "text": format!("{}: {}", issue.title, issue.description) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": issue.file_path },
+                        "region": { "startLine": issue.start_line.max(1) },
+                    }
+                }],
+            })
+        })
+        .collect();
+
+    let sarif = json!({
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "code-auditor",
+                    "informationUri": "https://github.com/kayneai/code-auditor",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+
+    Ok(serde_json::to_string_pretty(&sarif)?)
+}
+
+/// Map our severity levels onto the SARIF `level` vocabulary.
+fn sarif_level(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+/// Collect the distinct issue categories, sorted, so each one produces a
+/// single SARIF rule regardless of how many issues map to it.
+fn dedupe_categories<'a>(issues: &[&'a Issue]) -> Vec<&'a str> {
+    let mut categories: Vec<&str> = issues.iter().map(|i| i.category.as_str()).collect();
+    categories.sort_unstable();
+    categories.dedup();
+    categories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(category: &str, severity: Severity) -> Issue {
+        Issue {
+            file_path: "src/lib.rs".to_string(),
+            start_line: 1,
+            end_line: None,
+            severity,
+            category: category.to_string(),
+            title: "Example issue".to_string(),
+            description: "An example issue for tests.".to_string(),
+            suggestion: None,
+            code_snippet: None,
+        }
+    }
+
+    #[test]
+    fn test_sarif_level_mapping() {
+        assert_eq!(sarif_level(&Severity::Critical), "error");
+        assert_eq!(sarif_level(&Severity::High), "error");
+        assert_eq!(sarif_level(&Severity::Medium), "warning");
+        assert_eq!(sarif_level(&Severity::Low), "note");
+    }
+
+    #[test]
+    fn test_dedupe_categories_sorts_and_dedups() {
+        let issues = vec![
+            issue("security", Severity::High),
+            issue("style", Severity::Low),
+            issue("security", Severity::Critical),
+        ];
+        let refs: Vec<&Issue> = issues.iter().collect();
+        assert_eq!(dedupe_categories(&refs), vec!["security", "style"]);
+    }
+
+    #[test]
+    fn test_dedupe_categories_empty() {
+        let refs: Vec<&Issue> = Vec::new();
+        assert!(dedupe_categories(&refs).is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+//! Sandboxed cloning for untrusted repositories (`--sandbox`).
+//!
+//! Runs `git clone` inside an ephemeral container (Docker or Podman,
+//! autodetected) so that pointing the auditor at a hostile repository can't
+//! touch the host network or exfiltrate secrets during the clone itself.
+//! The cloned tree is bind-mounted out to a host temp directory and marked
+//! read-only before the rest of the pipeline reads from it.
+//!
+//! This module isolates the clone only. `--sandbox` does not sandbox the
+//! scanner/agent steps that follow: those still read the checked-out files
+//! and talk to Ollama directly on the host, the same as without `--sandbox`.
+//! Use it to stop a hostile clone hook or submodule from reaching the host;
+//! it does not protect against a hostile file being fed to the model.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Configuration for a sandboxed clone.
+#[derive(Debug, Clone)]
+pub struct SandboxConfig {
+    pub image: String,
+    pub cpu_limit: String,
+    pub memory_limit: String,
+    /// Strip write permission from the clone on the host once it's out of
+    /// the container. A host-side permission hardening step, not a network
+    /// control — the clone container itself still needs network access to
+    /// fetch, so there is nothing in this flow to deny network to.
+    pub read_only_after_clone: bool,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            image: "debian:bookworm-slim".to_string(),
+            cpu_limit: "2".to_string(),
+            memory_limit: "2g".to_string(),
+            read_only_after_clone: true,
+        }
+    }
+}
+
+/// The container runtime to use, autodetected by probing `docker`/`podman` on `PATH`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContainerRuntime {
+    Docker,
+    Podman,
+}
+
+impl ContainerRuntime {
+    fn binary(self) -> &'static str {
+        match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Podman => "podman",
+        }
+    }
+}
+
+/// Probe `PATH` for `docker`, then `podman`. Errors if neither is installed.
+fn detect_runtime() -> Result<ContainerRuntime> {
+    for runtime in [ContainerRuntime::Docker, ContainerRuntime::Podman] {
+        let found = Command::new(runtime.binary())
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if found {
+            return Ok(runtime);
+        }
+    }
+    bail!("--sandbox requires docker or podman on PATH, and neither was found")
+}
+
+/// Clone `repo_url` inside an ephemeral, resource-limited container and
+/// return the host path it was bind-mounted to. Only the clone runs
+/// containerized; the directory is marked read-only once the container
+/// exits, but the scanner/agent steps that follow still read it on the host
+/// like any other clone.
+pub fn sandboxed_clone(repo_url: &str, branch: Option<&str>, config: &SandboxConfig) -> Result<PathBuf> {
+    let runtime = detect_runtime()?;
+
+    let host_dir = std::env::temp_dir().join(format!("code-auditor-sandbox-{}", std::process::id()));
+    std::fs::create_dir_all(&host_dir)
+        .with_context(|| format!("Failed to create sandbox directory {}", host_dir.display()))?;
+
+    let mut clone_args = vec!["clone".to_string(), "--depth".to_string(), "1".to_string()];
+    if let Some(branch) = branch {
+        clone_args.push("--branch".to_string());
+        clone_args.push(branch.to_string());
+    }
+    clone_args.push(repo_url.to_string());
+    clone_args.push("/workspace/repo".to_string());
+
+    let status = Command::new(runtime.binary())
+        .args(["run", "--rm"])
+        .args(["--cpus", &config.cpu_limit])
+        .args(["--memory", &config.memory_limit])
+        .args(["-v", &format!("{}:/workspace", host_dir.display())])
+        .arg(&config.image)
+        .arg("git")
+        .args(&clone_args)
+        .status()
+        .with_context(|| format!("Failed to run {} for the sandboxed clone", runtime.binary()))?;
+
+    if !status.success() {
+        bail!("Sandboxed clone via {} failed", runtime.binary());
+    }
+
+    let repo_path = host_dir.join("repo");
+    if config.read_only_after_clone {
+        mark_read_only(&repo_path);
+    }
+
+    Ok(repo_path)
+}
+
+/// Best-effort: recursively strip write permission so the rest of the
+/// pipeline can only read the sandboxed clone. Failures are ignored; this
+/// is a defense-in-depth measure, not the sandbox boundary itself.
+#[cfg(unix)]
+fn mark_read_only(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    for entry in walk(path) {
+        if let Ok(metadata) = entry.metadata() {
+            let mut perms = metadata.permissions();
+            perms.set_mode(perms.mode() & !0o222);
+            let _ = std::fs::set_permissions(&entry, perms);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn mark_read_only(_path: &Path) {}
+
+fn walk(path: &Path) -> Vec<PathBuf> {
+    let mut out = vec![path.to_path_buf()];
+    if path.is_dir() {
+        if let Ok(entries) = std::fs::read_dir(path) {
+            for entry in entries.flatten() {
+                out.extend(walk(&entry.path()));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = SandboxConfig::default();
+        assert_eq!(config.cpu_limit, "2");
+        assert_eq!(config.memory_limit, "2g");
+        assert!(config.read_only_after_clone);
+    }
+}